@@ -0,0 +1,225 @@
+//! Client library for the `simple_token` Hyle contract: wraps
+//! [`ApiHttpClient`] with the register/transfer/prove flow used by the
+//! `host` CLI binary.
+
+pub mod migration;
+pub mod profiling;
+pub mod prover;
+pub mod signer;
+pub mod watcher;
+
+use std::time::Duration;
+
+use anyhow::bail;
+use anyhow::Context;
+use anyhow::Result;
+use contract::Token;
+use hyle::model::BlobTransaction;
+use hyle::model::ProofData;
+use hyle::model::ProofTransaction;
+use hyle::model::RegisterContractTransaction;
+use hyle::tools::rest_api_client::ApiHttpClient;
+use prover::{LocalProver, Prover};
+use risc0_zkvm::Receipt;
+use sdk::HyleOutput;
+use sdk::{ContractInput, ContractName};
+use signer::Signer;
+
+// These constants represent the RISC-V ELF and the image ID generated by risc0-build.
+// The ELF is used for proving and the ID is used for verification.
+use methods::{GUEST_ELF, GUEST_ID};
+
+/// Client for registering and interacting with a `simple_token` contract
+/// deployed on a Hyle node.
+pub struct TokenClient {
+    api: ApiHttpClient,
+    contract_name: ContractName,
+    prover: Box<dyn Prover>,
+}
+
+impl TokenClient {
+    pub fn new(host: String, contract_name: impl Into<ContractName>) -> Self {
+        Self::with_prover(host, contract_name, Box::new(LocalProver))
+    }
+
+    /// Same as [`Self::new`], but proves using the given [`Prover`] backend
+    /// instead of always proving locally.
+    pub fn with_prover(
+        host: String,
+        contract_name: impl Into<ContractName>,
+        prover: Box<dyn Prover>,
+    ) -> Self {
+        Self {
+            api: ApiHttpClient::new(host),
+            contract_name: contract_name.into(),
+            prover,
+        }
+    }
+
+    /// Registers a new `simple_token` contract with the given initial supply,
+    /// minted to the `faucet.<contract_name>` identity. Returns the tx hash.
+    pub async fn register(&self, supply: u128) -> Result<String> {
+        let initial_state = Token::new(
+            supply,
+            format!("faucet.{}", self.contract_name.0).into(),
+        );
+
+        println!("Initial state: {:?}", initial_state);
+        let state_digest = sdk::StateDigest(migration::VersionedState::encode(
+            migration::CURRENT_VERSION,
+            &initial_state,
+        )?);
+
+        let res = self
+            .api
+            .send_tx_register_contract(&RegisterContractTransaction {
+                owner: "examples".to_string(),
+                verifier: "risc0".into(),
+                program_id: sdk::ProgramId(sdk::to_u8_array(&GUEST_ID).to_vec()),
+                state_digest,
+                contract_name: self.contract_name.clone(),
+            })
+            .await?;
+        Ok(res.text().await?)
+    }
+
+    /// Fetches the current on-chain state and builds the `ContractInput` for
+    /// a transfer to `to`, signed by `signer`, without proving or submitting
+    /// it.
+    pub async fn build_transfer_input(
+        &self,
+        signer: &dyn Signer,
+        to: String,
+        amount: u128,
+    ) -> Result<ContractInput<Token>> {
+        let raw_state = self.api.get_contract(&self.contract_name).await?.state;
+        let initial_state = migration::migrate(
+            migration::VersionedState::decode(&raw_state.0)?,
+            &migration::registered_migrations(),
+        )?;
+
+        let action = sdk::erc20::ERC20Action::Transfer {
+            recipient: to.clone(),
+            amount,
+        };
+
+        let blobs = vec![sdk::Blob {
+            contract_name: self.contract_name.clone(),
+            data: sdk::BlobData(
+                bincode::encode_to_vec(action, bincode::config::standard())
+                    .expect("failed to encode BlobData"),
+            ),
+        }];
+
+        let signature = signer
+            .sign(&bincode::encode_to_vec(&blobs, bincode::config::standard())
+                .expect("failed to encode blobs for signing"))
+            .context("failed to sign transfer blob")?;
+
+        Ok(ContractInput::<Token> {
+            initial_state,
+            identity: signer.identity().into(),
+            tx_hash: "".into(),
+            private_blob: sdk::BlobData(signature),
+            blobs,
+            index: sdk::BlobIndex(0),
+        })
+    }
+
+    /// Fetches the current state, proves a transfer signed by `signer` to
+    /// `to`, and submits the blob and proof transactions. Returns the blob
+    /// and proof tx hashes.
+    pub async fn transfer(
+        &self,
+        signer: &dyn Signer,
+        to: String,
+        amount: u128,
+        reproducible: bool,
+    ) -> Result<(String, String)> {
+        let inputs = self.build_transfer_input(signer, to, amount).await?;
+        let blobs = inputs.blobs.clone();
+        let identity = inputs.identity.clone();
+
+        let receipt = self.prove(reproducible, inputs)?;
+
+        let blob_tx_hash = self
+            .api
+            .send_tx_blob(&BlobTransaction { identity, blobs })
+            .await?;
+
+        let proof_tx_hash = self
+            .api
+            .send_tx_proof(&ProofTransaction {
+                blob_tx_hash: blob_tx_hash.clone(),
+                proof: ProofData::Bytes(
+                    borsh::to_vec(&receipt).expect("Unable to encode receipt"),
+                ),
+                contract_name: self.contract_name.clone(),
+            })
+            .await?
+            .text()
+            .await?;
+
+        Ok((blob_tx_hash, proof_tx_hash))
+    }
+
+    /// Polls the node for the settlement status of `blob_tx_hash` every
+    /// `interval` until it reaches a final state or `timeout` elapses.
+    pub async fn wait_for_settlement(
+        &self,
+        blob_tx_hash: &str,
+        interval: Duration,
+        timeout: Duration,
+    ) -> Result<watcher::SettlementStatus> {
+        watcher::wait_for_settlement(&self.api, blob_tx_hash, interval, timeout).await
+    }
+
+    /// Reads the guest ELF to execute: the Docker-reproducible build output
+    /// when `reproducible` is set, otherwise the embedded `GUEST_ELF`.
+    fn resolve_elf(&self, reproducible: bool) -> Vec<u8> {
+        if reproducible {
+            std::fs::read("target/riscv-guest/riscv32im-risc0-zkvm-elf/docker/method/method")
+                .expect("Could not read ELF binary at target/riscv-guest/riscv32im-risc0-zkvm-elf/docker/method/method")
+        } else {
+            GUEST_ELF.to_vec()
+        }
+    }
+
+    /// Executes the guest with profiling enabled and writes a flamegraph SVG
+    /// to `flamegraph_path`, without generating a full proof.
+    pub fn profile(
+        &self,
+        reproducible: bool,
+        input: ContractInput<Token>,
+        flamegraph_path: &std::path::Path,
+    ) -> Result<profiling::ProfileReport> {
+        let binary = self.resolve_elf(reproducible);
+        profiling::profile(&input, &binary, flamegraph_path)
+    }
+
+    /// Runs the guest program against `input` and returns the resulting
+    /// receipt, failing if the guest reported an unsuccessful execution.
+    pub fn prove(&self, reproducible: bool, input: ContractInput<Token>) -> Result<Receipt> {
+        let input_bytes = risc0_zkvm::serde::to_vec(&input)
+            .context("failed to serialize guest input")?;
+        let input_bytes = bytemuck::cast_slice(&input_bytes);
+
+        let binary = self.resolve_elf(reproducible);
+        let receipt = self.prover.prove(input_bytes, &binary)?;
+
+        let hyle_output = receipt
+            .journal
+            .decode::<HyleOutput>()
+            .expect("Failed to decode journal");
+
+        if !hyle_output.success {
+            let program_error = std::str::from_utf8(&hyle_output.program_outputs).unwrap();
+            println!(
+                "\x1b[91mExecution failed ! Program output: {}\x1b[0m",
+                program_error
+            );
+            bail!("Execution failed");
+        }
+        Ok(receipt)
+    }
+}
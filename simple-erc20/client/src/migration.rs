@@ -0,0 +1,148 @@
+//! Versioned `Token` state and schema migration, run on fetched state before
+//! it is used to build a `ContractInput`.
+//!
+//! NOTE: migration runs client-side only, in [`crate::TokenClient::build_transfer_input`].
+//! The guest does not verify that the pre-migration state actually matches
+//! the committed on-chain digest, or re-run the migration itself, so this
+//! does not stop a malicious prover from skipping or forging it.
+
+use anyhow::{Context, Result};
+use contract::Token;
+
+/// Current on-chain schema version of the `Token` contract state.
+pub const CURRENT_VERSION: u32 = 1;
+
+/// An upgrade from `from_version` to `from_version + 1`.
+pub struct Migration {
+    pub from_version: u32,
+    pub upgrade: fn(Token) -> Token,
+}
+
+/// `Token` state as fetched from the node, tagged with the schema version it
+/// was written under.
+pub struct VersionedState {
+    pub version: u32,
+    pub token: Token,
+}
+
+impl VersionedState {
+    /// Decodes a version tag packed as a little-endian `u32` prefix in front
+    /// of the borsh-encoded `Token`, as written by [`Self::encode`]. Falls
+    /// back to treating `bytes` as a bare, un-prefixed `Token` at version 0
+    /// for state written before this versioning scheme existed.
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        if let Some(versioned) = Self::try_decode_versioned(bytes) {
+            return Ok(versioned);
+        }
+        let token: Token =
+            borsh::from_slice(bytes).context("failed to decode legacy (unversioned) Token state")?;
+        Ok(Self { version: 0, token })
+    }
+
+    fn try_decode_versioned(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 4 {
+            return None;
+        }
+        let (version_bytes, token_bytes) = bytes.split_at(4);
+        let version = u32::from_le_bytes(version_bytes.try_into().unwrap());
+        if version > CURRENT_VERSION {
+            return None;
+        }
+        let token: Token = borsh::from_slice(token_bytes).ok()?;
+        Some(Self { version, token })
+    }
+
+    /// Encodes a version tag and `Token` state for persistence on-chain.
+    pub fn encode(version: u32, token: &Token) -> Result<Vec<u8>> {
+        let mut bytes = version.to_le_bytes().to_vec();
+        bytes.extend(borsh::to_vec(token).context("failed to encode Token state")?);
+        Ok(bytes)
+    }
+}
+
+/// Runs every registered migration needed to bring `state` up to
+/// [`CURRENT_VERSION`], in order, and returns the up-to-date `Token`. Fails
+/// rather than silently returning a stale `Token` if no migration is
+/// registered for the state's version.
+pub fn migrate(state: VersionedState, migrations: &[Migration]) -> Result<Token> {
+    let mut version = state.version;
+    let mut token = state.token;
+    while version < CURRENT_VERSION {
+        let migration = migrations
+            .iter()
+            .find(|m| m.from_version == version)
+            .with_context(|| {
+                format!(
+                    "no migration registered to upgrade Token state from version {} (current: {})",
+                    version, CURRENT_VERSION
+                )
+            })?;
+        token = (migration.upgrade)(token);
+        version += 1;
+    }
+    Ok(token)
+}
+
+/// Migrations registered for the `Token` contract, in ascending
+/// `from_version` order. Empty until the `Token` layout gains its first
+/// breaking change.
+pub fn registered_migrations() -> Vec<Migration> {
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_roundtrip() {
+        let token = Token::new(100, "faucet.simple_token".into());
+        let bytes = VersionedState::encode(CURRENT_VERSION, &token).unwrap();
+        let decoded = VersionedState::decode(&bytes).unwrap();
+        assert_eq!(decoded.version, CURRENT_VERSION);
+        assert_eq!(decoded.token, token);
+    }
+
+    #[test]
+    fn decode_falls_back_to_legacy_unversioned_state() {
+        let token = Token::new(42, "faucet.simple_token".into());
+        let bytes = borsh::to_vec(&token).unwrap();
+        let decoded = VersionedState::decode(&bytes).unwrap();
+        assert_eq!(decoded.version, 0);
+        assert_eq!(decoded.token, token);
+    }
+
+    #[test]
+    fn migrate_is_noop_at_current_version() {
+        let token = Token::new(7, "faucet.simple_token".into());
+        let state = VersionedState {
+            version: CURRENT_VERSION,
+            token: token.clone(),
+        };
+        assert_eq!(migrate(state, &[]).unwrap(), token);
+    }
+
+    #[test]
+    fn migrate_runs_registered_upgrade() {
+        fn bump_supply(mut token: Token) -> Token {
+            token.total_supply += 1;
+            token
+        }
+
+        let token = Token::new(7, "faucet.simple_token".into());
+        let state = VersionedState { version: 0, token };
+        let migrations = vec![Migration {
+            from_version: 0,
+            upgrade: bump_supply,
+        }];
+        let migrated = migrate(state, &migrations).unwrap();
+        assert_eq!(migrated.total_supply, 8);
+    }
+
+    #[test]
+    fn migrate_errors_when_no_migration_is_registered() {
+        let token = Token::new(7, "faucet.simple_token".into());
+        let state = VersionedState { version: 0, token };
+        assert!(migrate(state, &[]).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+//! Runs the guest through the RISC Zero executor with profiling enabled and
+//! renders the resulting folded stacks as a flamegraph, without proving.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use risc0_zkvm::{default_executor, ExecutorEnv};
+
+/// Per-segment cycle counts recorded while executing the guest.
+pub struct SegmentCycles {
+    pub index: usize,
+    pub cycles: u64,
+}
+
+/// Result of a profiling run: overall cycle counts plus a path to the
+/// generated folded-stack flamegraph SVG.
+pub struct ProfileReport {
+    pub total_cycles: u64,
+    pub segments: Vec<SegmentCycles>,
+    pub flamegraph_path: std::path::PathBuf,
+}
+
+/// Executes `elf` against the given input with profiling enabled, writing a
+/// flamegraph SVG to `flamegraph_path`.
+pub fn profile<T: serde::Serialize>(
+    input: &T,
+    elf: &[u8],
+    flamegraph_path: &Path,
+) -> Result<ProfileReport> {
+    let profiler = std::rc::Rc::new(std::cell::RefCell::new(
+        risc0_zkvm::Profiler::new("guest", elf).context("failed to start guest profiler")?,
+    ));
+
+    let env = ExecutorEnv::builder()
+        .write(input)
+        .unwrap()
+        .trace_callback(profiler.borrow_mut().make_trace_callback())
+        .build()
+        .unwrap();
+
+    let session = default_executor()
+        .execute(env, elf)
+        .context("guest execution failed")?;
+
+    let mut profiler = std::rc::Rc::try_unwrap(profiler)
+        .map_err(|_| anyhow::anyhow!("profiler still has outstanding references"))?
+        .into_inner();
+    profiler.finalize();
+
+    let folded = profiler
+        .to_folded_stacks()
+        .context("failed to fold profiler stacks")?;
+
+    let mut svg = Vec::new();
+    inferno::flamegraph::from_lines(
+        &mut inferno::flamegraph::Options::default(),
+        folded.iter().map(String::as_str),
+        &mut svg,
+    )
+    .context("failed to render flamegraph")?;
+    std::fs::write(flamegraph_path, svg)
+        .with_context(|| format!("failed to write flamegraph to {}", flamegraph_path.display()))?;
+
+    let segments = session
+        .segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| SegmentCycles {
+            index,
+            cycles: segment.cycles(),
+        })
+        .collect::<Vec<_>>();
+    let total_cycles = segments.iter().map(|s| s.cycles).sum();
+
+    Ok(ProfileReport {
+        total_cycles,
+        segments,
+        flamegraph_path: flamegraph_path.to_path_buf(),
+    })
+}
@@ -0,0 +1,115 @@
+//! Pluggable proving backends, selected by [`crate::TokenClient`].
+
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use risc0_zkvm::{default_prover, ExecutorEnv, Receipt};
+
+/// Default time budget for a remote proving session to reach a terminal
+/// status before `RemoteProver::prove` gives up.
+const DEFAULT_POLL_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Computes a RISC Zero [`Receipt`] for already-serialized guest input bytes
+/// and an ELF binary.
+pub trait Prover {
+    fn prove(&self, input_bytes: &[u8], elf: &[u8]) -> Result<Receipt>;
+}
+
+/// Proves locally using the machine's default RISC Zero prover.
+pub struct LocalProver;
+
+impl Prover for LocalProver {
+    fn prove(&self, input_bytes: &[u8], elf: &[u8]) -> Result<Receipt> {
+        let env = ExecutorEnv::builder()
+            .write_slice(input_bytes)
+            .build()
+            .context("failed to build executor env")?;
+        Ok(default_prover().prove(env, elf)?.receipt)
+    }
+}
+
+/// Proves by submitting the input bytes and ELF to a hosted Bonsai-style
+/// proving service and polling for the resulting receipt.
+pub struct RemoteProver {
+    endpoint: String,
+    api_key: String,
+    poll_timeout: Duration,
+}
+
+impl RemoteProver {
+    pub fn new(endpoint: String, api_key: String) -> Self {
+        Self {
+            endpoint,
+            api_key,
+            poll_timeout: DEFAULT_POLL_TIMEOUT,
+        }
+    }
+
+    /// Overrides how long to wait for the Bonsai session to reach a terminal
+    /// status before giving up. Defaults to 10 minutes.
+    pub fn with_poll_timeout(mut self, poll_timeout: Duration) -> Self {
+        self.poll_timeout = poll_timeout;
+        self
+    }
+}
+
+impl Prover for RemoteProver {
+    fn prove(&self, input_bytes: &[u8], elf: &[u8]) -> Result<Receipt> {
+        let client = bonsai_sdk::blocking::Client::from_parts(
+            self.endpoint.clone(),
+            self.api_key.clone(),
+            risc0_zkvm::VERSION,
+        )
+        .context("failed to build Bonsai client")?;
+
+        let image_id = hex::encode(risc0_zkvm::compute_image_id(elf)?);
+        client
+            .upload_img(&image_id, elf.to_vec())
+            .context("failed to upload ELF to Bonsai")?;
+
+        let input_id = client
+            .upload_input(input_bytes.to_vec())
+            .context("failed to upload input to Bonsai")?;
+
+        let session = client
+            .create_session(image_id, input_id, vec![], false)
+            .context("failed to create Bonsai session")?;
+
+        let deadline = Instant::now() + self.poll_timeout;
+        loop {
+            let status = session
+                .status(&client)
+                .context("failed to poll Bonsai session status")?;
+            match status.status.as_str() {
+                "RUNNING" => {
+                    if Instant::now() >= deadline {
+                        anyhow::bail!(
+                            "timed out after {:?} waiting for Bonsai session to complete",
+                            self.poll_timeout
+                        );
+                    }
+                    std::thread::sleep(Duration::from_secs(5));
+                    continue;
+                }
+                "SUCCEEDED" => {
+                    let receipt_url = status
+                        .receipt_url
+                        .context("Bonsai session succeeded without a receipt URL")?;
+                    let receipt_bytes = client
+                        .download(&receipt_url)
+                        .context("failed to download receipt from Bonsai")?;
+                    return bincode::decode_from_slice(&receipt_bytes, bincode::config::standard())
+                        .map(|(receipt, _)| receipt)
+                        .context("failed to decode receipt from Bonsai");
+                }
+                other => {
+                    anyhow::bail!(
+                        "Bonsai session failed: {} ({})",
+                        other,
+                        status.error_msg.unwrap_or_default()
+                    );
+                }
+            }
+        }
+    }
+}
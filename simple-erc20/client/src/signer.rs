@@ -0,0 +1,97 @@
+//! Produces a signature over a transfer's blob payload, carried in the
+//! transaction's private blob alongside the claimed `identity`.
+//!
+//! NOTE: this crate only produces and attaches the signature. The `Token`
+//! guest (in the `contract` crate, not part of this repo slice) does not yet
+//! verify it against the claimed identity, so `BlobTransaction::identity` is
+//! still unauthenticated until that guest-side check is added.
+
+use anyhow::{Context, Result};
+use ed25519_dalek::{Signer as _, SigningKey, VerifyingKey};
+
+/// Produces a signature over a blob payload, and reports the identity that
+/// signature is claimed for.
+pub trait Signer {
+    /// The identity to put in `BlobTransaction::identity` / `ContractInput::identity`.
+    fn identity(&self) -> String;
+
+    /// Signs `payload` (the encoded blob data), returning the raw signature
+    /// bytes to carry in the transaction's private blob.
+    fn sign(&self, payload: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Signs using an ed25519 keypair loaded from a local keystore file.
+pub struct LocalKeystoreSigner {
+    identity: String,
+    signing_key: SigningKey,
+}
+
+impl LocalKeystoreSigner {
+    /// Loads a 32-byte ed25519 seed from `keystore_path` and binds it to
+    /// `identity`.
+    pub fn from_file(keystore_path: &std::path::Path, identity: String) -> Result<Self> {
+        let seed_bytes = std::fs::read(keystore_path)
+            .with_context(|| format!("failed to read keystore at {}", keystore_path.display()))?;
+        let seed: [u8; 32] = seed_bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("keystore file must contain a 32-byte ed25519 seed"))?;
+        Ok(Self {
+            identity,
+            signing_key: SigningKey::from_bytes(&seed),
+        })
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+}
+
+impl Signer for LocalKeystoreSigner {
+    fn identity(&self) -> String {
+        self.identity.clone()
+    }
+
+    fn sign(&self, payload: &[u8]) -> Result<Vec<u8>> {
+        Ok(self.signing_key.sign(payload).to_bytes().to_vec())
+    }
+}
+
+/// Signs using a Ledger-style hardware wallet connected over USB HID.
+pub struct LedgerSigner {
+    identity: String,
+    transport: ledger_transport_hid::TransportNativeHID,
+}
+
+impl LedgerSigner {
+    /// Connects to the first available Ledger device and binds it to
+    /// `identity`.
+    pub fn connect(identity: String) -> Result<Self> {
+        let hidapi = ledger_transport_hid::hidapi::HidApi::new()
+            .context("failed to initialize HID API for Ledger device discovery")?;
+        let transport = ledger_transport_hid::TransportNativeHID::new(&hidapi)
+            .context("failed to connect to Ledger device")?;
+        Ok(Self { identity, transport })
+    }
+}
+
+impl Signer for LedgerSigner {
+    fn identity(&self) -> String {
+        self.identity.clone()
+    }
+
+    fn sign(&self, payload: &[u8]) -> Result<Vec<u8>> {
+        // CLA/INS values are placeholders for the Hyle signing Ledger app.
+        let command = ledger_transport_hid::apdu::ApduCommand {
+            cla: 0xe0,
+            ins: 0x02,
+            p1: 0x00,
+            p2: 0x00,
+            data: payload.to_vec(),
+        };
+        let response = self
+            .transport
+            .exchange(&command)
+            .context("Ledger device rejected the signing request")?;
+        Ok(response.data().to_vec())
+    }
+}
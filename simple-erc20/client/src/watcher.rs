@@ -0,0 +1,105 @@
+//! Polls a Hyle node for the settlement status of a submitted blob transaction.
+
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+
+/// Settlement status of a blob transaction, as reported by the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettlementStatus {
+    Sequenced,
+    Success,
+    Failure,
+}
+
+impl SettlementStatus {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().trim_matches('"') {
+            "Sequenced" | "WaitingDissemination" | "DataProposalCreated" => Some(Self::Sequenced),
+            "Success" | "Settled" => Some(Self::Success),
+            "Failure" | "TimedOut" => Some(Self::Failure),
+            _ => None,
+        }
+    }
+
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Success | Self::Failure)
+    }
+}
+
+/// Polls `api` for the status of `tx_hash` every `interval`, printing
+/// transitions, until it reaches a final state or `timeout` elapses.
+pub async fn wait_for_settlement(
+    api: &hyle::tools::rest_api_client::ApiHttpClient,
+    tx_hash: &str,
+    interval: Duration,
+    timeout: Duration,
+) -> Result<SettlementStatus> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut last_status = None;
+
+    loop {
+        let raw = api.get_tx_status(tx_hash).await?.text().await?;
+        if let Some(status) = SettlementStatus::parse(&raw) {
+            if Some(status) != last_status {
+                println!("⏳ Tx {} status: {:?}", tx_hash, status);
+                last_status = Some(status);
+            }
+            if status.is_final() {
+                return Ok(status);
+            }
+        }
+
+        if tokio::time::Instant::now() >= deadline {
+            bail!(
+                "timed out waiting for tx {} to settle (last status: {:?})",
+                tx_hash,
+                last_status
+            );
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sequenced_aliases() {
+        assert_eq!(SettlementStatus::parse("Sequenced"), Some(SettlementStatus::Sequenced));
+        assert_eq!(
+            SettlementStatus::parse("WaitingDissemination"),
+            Some(SettlementStatus::Sequenced)
+        );
+        assert_eq!(
+            SettlementStatus::parse("DataProposalCreated"),
+            Some(SettlementStatus::Sequenced)
+        );
+    }
+
+    #[test]
+    fn parses_final_states() {
+        assert_eq!(SettlementStatus::parse("Success"), Some(SettlementStatus::Success));
+        assert_eq!(SettlementStatus::parse("Settled"), Some(SettlementStatus::Success));
+        assert_eq!(SettlementStatus::parse("Failure"), Some(SettlementStatus::Failure));
+        assert_eq!(SettlementStatus::parse("TimedOut"), Some(SettlementStatus::Failure));
+    }
+
+    #[test]
+    fn trims_quotes_and_whitespace() {
+        assert_eq!(SettlementStatus::parse(" \"Success\" "), Some(SettlementStatus::Success));
+    }
+
+    #[test]
+    fn unknown_status_is_none() {
+        assert_eq!(SettlementStatus::parse("Bogus"), None);
+    }
+
+    #[test]
+    fn is_final_matches_success_and_failure_only() {
+        assert!(!SettlementStatus::Sequenced.is_final());
+        assert!(SettlementStatus::Success.is_final());
+        assert!(SettlementStatus::Failure.is_final());
+    }
+}
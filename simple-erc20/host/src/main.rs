@@ -1,19 +1,11 @@
-use anyhow::bail;
-use anyhow::Result;
-use clap::{Parser, Subcommand};
-use contract::Token;
-use hyle::model::BlobTransaction;
-use hyle::model::ProofData;
-use hyle::model::ProofTransaction;
-use hyle::model::RegisterContractTransaction;
-use risc0_zkvm::Receipt;
-use risc0_zkvm::{default_prover, ExecutorEnv};
-use sdk::HyleOutput;
-use sdk::{ContractInput, Digestable};
-
-// These constants represent the RISC-V ELF and the image ID generated by risc0-build.
-// The ELF is used for proving and the ID is used for verification.
-use methods::{GUEST_ELF, GUEST_ID};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use hyle_token_client::prover::{LocalProver, Prover, RemoteProver};
+use hyle_token_client::signer::{LedgerSigner, LocalKeystoreSigner, Signer};
+use hyle_token_client::watcher::SettlementStatus;
+use hyle_token_client::TokenClient;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -30,6 +22,60 @@ struct Cli {
 
     #[arg(long, default_value = "simple_token")]
     pub contract_name: String,
+
+    /// Where to compute the RISC Zero proof.
+    #[arg(long, value_enum, default_value_t = ProverKind::Local)]
+    pub prover: ProverKind,
+
+    /// Bonsai-compatible proving service endpoint, used when `--prover remote`.
+    #[arg(long, env = "BONSAI_API_URL")]
+    pub bonsai_url: Option<String>,
+
+    /// API key for the proving service, used when `--prover remote`.
+    #[arg(long, env = "BONSAI_API_KEY")]
+    pub bonsai_api_key: Option<String>,
+
+    /// After submitting a transfer, poll the node until it settles instead
+    /// of exiting immediately.
+    #[arg(long)]
+    pub wait: bool,
+
+    /// How often to poll for settlement, in seconds, when `--wait` is set.
+    #[arg(long, default_value_t = 2)]
+    pub poll_interval_secs: u64,
+
+    /// Give up waiting for settlement after this many seconds. Also used as
+    /// the poll timeout for `--prover remote`.
+    #[arg(long, default_value_t = 60)]
+    pub timeout_secs: u64,
+
+    /// Instead of proving, execute the guest with profiling enabled and
+    /// write a flamegraph SVG to the given path.
+    #[arg(long)]
+    pub profile: Option<std::path::PathBuf>,
+
+    /// Which key produces the transfer signature attached to the blob
+    /// (keystore file or connected Ledger-style hardware wallet). The guest
+    /// does not verify this signature yet, so it does NOT authenticate
+    /// `from` — anyone can still submit a transfer claiming any identity.
+    #[arg(long, value_enum, default_value_t = SignerKind::Keystore)]
+    pub signer: SignerKind,
+
+    /// Path to the ed25519 keystore file, used when `--signer keystore`.
+    #[arg(long, env = "HYLE_KEYSTORE_PATH")]
+    pub keystore_path: Option<std::path::PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum ProverKind {
+    Local,
+    Remote,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum SignerKind {
+    Keystore,
+    Ledger,
 }
 
 #[derive(Subcommand)]
@@ -45,7 +91,7 @@ enum Commands {
 }
 
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
     // Initialize tracing. In order to view logs, run `RUST_LOG=info cargo run`
     tracing_subscriber::fmt()
         .with_env_filter(tracing_subscriber::filter::EnvFilter::from_default_env())
@@ -59,119 +105,89 @@ async fn main() {
         println!("Running non-reproducibly");
     }
 
-    let client = hyle::tools::rest_api_client::ApiHttpClient::new(cli.host);
-
-    let contract_name = &cli.contract_name;
+    let wait = cli.wait;
+    let poll_interval = Duration::from_secs(cli.poll_interval_secs);
+    let timeout = Duration::from_secs(cli.timeout_secs);
+
+    let prover: Box<dyn Prover> = match cli.prover {
+        ProverKind::Local => Box::new(LocalProver),
+        ProverKind::Remote => Box::new(
+            RemoteProver::new(
+                cli.bonsai_url
+                    .expect("--bonsai-url (or BONSAI_API_URL) is required for --prover remote"),
+                cli.bonsai_api_key
+                    .expect("--bonsai-api-key (or BONSAI_API_KEY) is required for --prover remote"),
+            )
+            .with_poll_timeout(timeout),
+        ),
+    };
+    let client = TokenClient::with_prover(cli.host, cli.contract_name, prover);
 
     match cli.command {
         Commands::Register { supply } => {
-            let initial_state = Token::new(supply, format!("faucet.{}", contract_name).into());
-
-            println!("Initial state: {:?}", initial_state);
-            let initial_state = initial_state.as_digest();
-
-            let res = client
-                .send_tx_register_contract(&RegisterContractTransaction {
-                    owner: "examples".to_string(),
-                    verifier: "risc0".into(),
-                    program_id: sdk::ProgramId(sdk::to_u8_array(&GUEST_ID).to_vec()),
-                    state_digest: initial_state,
-                    contract_name: contract_name.clone().into(),
-                })
-                .await
-                .unwrap();
-            println!(
-                "✅ Register contract tx sent. Tx hash: {}",
-                res.text().await.unwrap()
-            );
+            let tx_hash = client.register(supply).await.unwrap();
+            println!("✅ Register contract tx sent. Tx hash: {}", tx_hash);
         }
         Commands::Transfer { from, to, amount } => {
-            let initial_state: Token = client
-                .get_contract(&contract_name.clone().into())
-                .await
-                .unwrap()
-                .state
-                .into();
-
-            let action = sdk::erc20::ERC20Action::Transfer {
-                recipient: to.clone(),
-                amount,
-            };
-
-            let blobs = vec![sdk::Blob {
-                contract_name: contract_name.clone().into(),
-                data: sdk::BlobData(
-                    bincode::encode_to_vec(action, bincode::config::standard())
-                        .expect("failed to encode BlobData"),
+            let signer: Box<dyn Signer> = match cli.signer {
+                SignerKind::Keystore => Box::new(
+                    LocalKeystoreSigner::from_file(
+                        &cli
+                            .keystore_path
+                            .expect("--keystore-path (or HYLE_KEYSTORE_PATH) is required for --signer keystore"),
+                        from,
+                    )
+                    .unwrap(),
                 ),
-            }];
-
-            let inputs = ContractInput::<Token> {
-                initial_state,
-                identity: from.clone().into(),
-                tx_hash: "".into(),
-                private_blob: sdk::BlobData(vec![]),
-                blobs: blobs.clone(),
-                index: sdk::BlobIndex(0),
+                SignerKind::Ledger => Box::new(LedgerSigner::connect(from).unwrap()),
             };
+            println!(
+                "\x1b[93m⚠ The guest does not verify this signature: `identity` is NOT authenticated. Do not rely on this for real authorization.\x1b[0m"
+            );
 
-            let receipt = prove(cli.reproducible, inputs).unwrap();
-
-            let blob_tx_hash = client
-                .send_tx_blob(&BlobTransaction {
-                    identity: from.into(),
-                    blobs,
-                })
+            if let Some(flamegraph_path) = cli.profile {
+                let inputs = client
+                    .build_transfer_input(signer.as_ref(), to, amount)
+                    .await
+                    .unwrap();
+                let report = client
+                    .profile(cli.reproducible, inputs, &flamegraph_path)
+                    .unwrap();
+                println!("🔥 Total cycles: {}", report.total_cycles);
+                for segment in &report.segments {
+                    println!("  segment {}: {} cycles", segment.index, segment.cycles);
+                }
+                println!("🔥 Flamegraph written to {}", report.flamegraph_path.display());
+                return ExitCode::SUCCESS;
+            }
+
+            let (blob_tx_hash, proof_tx_hash) = client
+                .transfer(signer.as_ref(), to, amount, cli.reproducible)
                 .await
                 .unwrap();
             println!("✅ Blob tx sent. Tx hash: {}", blob_tx_hash);
-
-            let proof_tx_hash = client
-                .send_tx_proof(&ProofTransaction {
-                    blob_tx_hash,
-                    proof: ProofData::Bytes(
-                        borsh::to_vec(&receipt).expect("Unable to encode receipt"),
-                    ),
-                    contract_name: contract_name.clone().into(),
-                })
-                .await
-                .unwrap();
-            println!(
-                "✅ Proof tx sent. Tx hash: {}",
-                proof_tx_hash.text().await.unwrap()
-            );
+            println!("✅ Proof tx sent. Tx hash: {}", proof_tx_hash);
+
+            if wait {
+                match client
+                    .wait_for_settlement(&blob_tx_hash, poll_interval, timeout)
+                    .await
+                {
+                    Ok(status) if status == SettlementStatus::Success => {
+                        println!("✅ Transfer settled.");
+                    }
+                    Ok(status) => {
+                        println!("\x1b[91m❌ Transfer did not settle: {:?}\x1b[0m", status);
+                        return ExitCode::FAILURE;
+                    }
+                    Err(err) => {
+                        println!("\x1b[91m❌ {:?}\x1b[0m", err);
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
         }
     }
-}
 
-fn prove(reproducible: bool, input: ContractInput<Token>) -> Result<Receipt> {
-    let env = ExecutorEnv::builder()
-        .write(&input)
-        .unwrap()
-        .build()
-        .unwrap();
-
-    let prover = default_prover();
-    let binary = if reproducible {
-        std::fs::read("target/riscv-guest/riscv32im-risc0-zkvm-elf/docker/method/method")
-            .expect("Could not read ELF binary at target/riscv-guest/riscv32im-risc0-zkvm-elf/docker/method/method")
-    } else {
-        GUEST_ELF.to_vec()
-    };
-    let receipt = prover.prove(env, &binary).unwrap().receipt;
-
-    let hyle_output = receipt
-        .journal
-        .decode::<HyleOutput>()
-        .expect("Failed to decode journal");
-
-    if !hyle_output.success {
-        let program_error = std::str::from_utf8(&hyle_output.program_outputs).unwrap();
-        println!(
-            "\x1b[91mExecution failed ! Program output: {}\x1b[0m",
-            program_error
-        );
-        bail!("Execution failed");
-    }
-    Ok(receipt)
+    ExitCode::SUCCESS
 }